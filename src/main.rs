@@ -5,15 +5,21 @@ use std::sync::{Arc, Mutex};
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use git2::Repository;
+use git2::build::RepoBuilder;
+use git2::{FetchOptions, Progress, RemoteCallbacks};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use humansize::{format_size, BINARY};
+use ignore::WalkBuilder;
 use lazy_static::lazy_static;
 use log::{debug, info, warn};
 use rayon::prelude::*;
 use regex::Regex;
+use serde::Deserialize;
 use tempfile::tempdir;
 use url::Url;
-use walkdir::{DirEntry, WalkDir};
+use walkdir::WalkDir;
+
+const DEFAULT_THRESHOLD: f32 = 0.1;
 
 lazy_static! {
   static ref SSH_REGEX: Regex =
@@ -37,19 +43,128 @@ struct Args {
   #[clap(short, long, value_parser)]
   output: Option<String>,
 
-  /// Set file size threshold in MB
-  #[clap(short, long, value_parser, default_value_t = 0.1)]
-  threshold: f32,
+  /// Set file size threshold in MB (defaults to 0.1, see .gitsnap.toml)
+  #[clap(short, long, value_parser)]
+  threshold: Option<f32>,
 
   /// Include all files regardless of size or type
   #[clap(long, value_parser)]
   include_all: bool,
 
+  /// Disable .gitignore filtering and walk every file like before
+  #[clap(long, value_parser)]
+  no_ignore: bool,
+
+  /// Output format: `text` (banner style, default) or `md` (GitHub-flavored Markdown)
+  #[clap(long, value_enum)]
+  format: Option<OutputFormat>,
+
+  /// Also snapshot the repository's GitHub wiki, if it has one
+  #[clap(long, value_parser)]
+  include_wiki: bool,
+
+  /// Only process files matching this glob (repeatable, e.g. `src/**/*.rs`)
+  #[clap(long, value_parser)]
+  include: Vec<String>,
+
+  /// Exclude files matching this glob (repeatable); always wins over --include
+  #[clap(long, value_parser)]
+  exclude: Vec<String>,
+
   /// Enable debug mode with verbose logging
   #[clap(long, value_parser)]
   debug: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum OutputFormat {
+  Text,
+  Md,
+}
+
+/// Bundles the filtering knobs threaded through the walk/exclude/wiki
+/// pipeline, so call sites pass one struct instead of a handful of
+/// positional bools/options that are easy to transpose by accident.
+struct FilterConfig<'a> {
+  threshold_bytes: u64,
+  include_all: bool,
+  no_ignore: bool,
+  include_set: Option<&'a GlobSet>,
+  exclude_set: Option<&'a GlobSet>,
+}
+
+/// Mirrors the CLI flags that can also be set via `.gitsnap.toml` / `.gitsnap.yaml`.
+/// Keys are kebab-case (e.g. `include-all`, not `include_all`); unknown
+/// fields are rejected so a mistyped or underscored key surfaces as a parse
+/// error instead of silently falling back to the default.
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+struct FileConfig {
+  threshold: Option<f32>,
+  include_all: Option<bool>,
+  output: Option<String>,
+  no_ignore: Option<bool>,
+  format: Option<OutputFormat>,
+  include_wiki: Option<bool>,
+  include: Option<Vec<String>>,
+  exclude: Option<Vec<String>>,
+}
+
+fn find_config_path(repo_dir: &Path) -> Option<PathBuf> {
+  let mut search_dirs = vec![repo_dir.to_path_buf()];
+  if let Ok(cwd) = std::env::current_dir() {
+    search_dirs.push(cwd);
+  }
+
+  for dir in search_dirs {
+    for name in [".gitsnap.toml", ".gitsnap.yaml", ".gitsnap.yml"] {
+      let candidate = dir.join(name);
+      if candidate.is_file() {
+        return Some(candidate);
+      }
+    }
+  }
+
+  None
+}
+
+fn load_config(repo_dir: &Path) -> Result<FileConfig> {
+  let Some(config_path) = find_config_path(repo_dir) else {
+    debug!("No .gitsnap config file found, using defaults");
+    return Ok(FileConfig::default());
+  };
+
+  info!("Loading config from: {}", config_path.display());
+  let contents = fs::read_to_string(&config_path)
+    .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+
+  let config = if config_path
+    .extension()
+    .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+  {
+    toml::from_str(&contents).context("Failed to parse .gitsnap.toml")?
+  } else {
+    serde_yaml::from_str(&contents).context("Failed to parse .gitsnap.yaml")?
+  };
+
+  Ok(config)
+}
+
+/// CLI flag wins when set, otherwise fall back to the config file value, then
+/// `false`. `name` is only used for the `--debug` precedence log line.
+fn resolve_bool_flag(name: &str, cli_value: bool, config_value: Option<bool>) -> bool {
+  if cli_value {
+    debug!("{name}: true (from CLI flag)");
+    true
+  } else if config_value.unwrap_or(false) {
+    debug!("{name}: true (from config file)");
+    true
+  } else {
+    false
+  }
+}
+
 fn normalize_repository_url(repo_input: &str) -> Result<String> {
   // Check if it's already a valid URL
   if let Ok(url) = Url::parse(repo_input) {
@@ -93,6 +208,36 @@ fn normalize_repository_url(repo_input: &str) -> Result<String> {
   )
 }
 
+fn print_progress(line: &str, last_shown: &mut Option<String>) {
+  if last_shown.as_deref() == Some(line) {
+    return;
+  }
+  eprint!("\r{line:<60}");
+  let _ = std::io::stderr().flush();
+  *last_shown = Some(line.to_string());
+}
+
+/// Percentage of `total` that `done` represents, as git2's progress callbacks
+/// report it (0 when `total` is 0, rather than dividing by zero).
+fn transfer_percent(done: usize, total: usize) -> usize {
+  (100 * done).checked_div(total).unwrap_or(0)
+}
+
+fn format_transfer_progress(progress: &Progress) -> String {
+  if progress.received_objects() < progress.total_objects() {
+    let percent = transfer_percent(progress.received_objects(), progress.total_objects());
+    format!(
+      "Downloading... {percent}% ({})",
+      format_size(progress.received_bytes() as u64, BINARY)
+    )
+  } else if progress.indexed_deltas() < progress.total_deltas() {
+    let percent = transfer_percent(progress.indexed_deltas(), progress.total_deltas());
+    format!("Resolving... {percent}%")
+  } else {
+    "Finishing up...".to_string()
+  }
+}
+
 fn clone_repository(url: &str, temp_dir: &Path) -> Result<()> {
   info!("Cloning repository: {url}");
   let git_url = if std::path::Path::new(url)
@@ -104,12 +249,93 @@ fn clone_repository(url: &str, temp_dir: &Path) -> Result<()> {
     format!("{url}.git")
   };
 
-  Repository::clone(&git_url, temp_dir).context("Failed to clone repository")?;
+  let mut last_shown = None;
+  let mut callbacks = RemoteCallbacks::new();
+  callbacks.transfer_progress(|progress| {
+    print_progress(&format_transfer_progress(&progress), &mut last_shown);
+    true
+  });
+
+  let mut fetch_options = FetchOptions::new();
+  fetch_options.remote_callbacks(callbacks);
+
+  RepoBuilder::new()
+    .fetch_options(fetch_options)
+    .clone(&git_url, temp_dir)
+    .context("Failed to clone repository")?;
+
+  eprintln!("\r{:<60}", "Finishing up...");
 
   info!("Repository cloned to: {}", temp_dir.display());
   Ok(())
 }
 
+fn clone_wiki_repository(repo_url: &str, temp_dir: &Path) -> Result<()> {
+  let wiki_url = format!("{repo_url}.wiki.git");
+  info!("Cloning wiki: {wiki_url}");
+
+  let mut fetch_options = FetchOptions::new();
+  fetch_options.depth(1);
+
+  RepoBuilder::new()
+    .fetch_options(fetch_options)
+    .clone(&wiki_url, temp_dir)
+    .context("Failed to clone wiki repository")?;
+
+  info!("Wiki cloned to: {}", temp_dir.display());
+  Ok(())
+}
+
+/// Appends the wiki's Markdown pages to an already-written snapshot, under a
+/// clearly marked "Wiki" section. Runs the same `--threshold`/`--no-ignore`/
+/// `--include`/`--exclude` filters as the main pipeline. Returns the number of
+/// pages appended.
+fn append_wiki_section(
+  wiki_dir: &Path,
+  output_path: &Path,
+  format: OutputFormat,
+  filters: &FilterConfig,
+) -> Result<usize> {
+  let mut pages = collect_valid_entries(wiki_dir, filters);
+  pages.retain(|path| {
+    path
+      .extension()
+      .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+  });
+  pages.sort();
+
+  if pages.is_empty() {
+    return Ok(0);
+  }
+
+  let mut output = fs::OpenOptions::new().append(true).open(output_path)?;
+
+  match format {
+    OutputFormat::Text => {
+      writeln!(
+        output,
+        "================================================================================"
+      )?;
+      writeln!(output, "Wiki")?;
+      writeln!(
+        output,
+        "================================================================================"
+      )?;
+      writeln!(output)?;
+    }
+    OutputFormat::Md => {
+      writeln!(output, "## Wiki")?;
+      writeln!(output)?;
+    }
+  }
+
+  for page in &pages {
+    write_file_entry(&mut output, wiki_dir, page, format)?;
+  }
+
+  Ok(pages.len())
+}
+
 fn is_binary_file(path: &Path) -> Result<bool> {
   let file = File::open(path)?;
   let mut reader = BufReader::with_capacity(8000, file);
@@ -126,9 +352,21 @@ fn is_binary_file(path: &Path) -> Result<bool> {
   Ok(false)
 }
 
-fn is_excluded_file(entry: &DirEntry, threshold_bytes: u64, include_all: bool) -> Result<bool> {
-  let path = entry.path();
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+  let mut builder = GlobSetBuilder::new();
+  for pattern in patterns {
+    let glob = Glob::new(pattern).with_context(|| format!("Invalid glob pattern: {pattern}"))?;
+    builder.add(glob);
+  }
+  builder.build().context("Failed to build glob matcher")
+}
 
+fn is_excluded_file(
+  path: &Path,
+  relative_path: &Path,
+  is_dir: bool,
+  filters: &FilterConfig,
+) -> Result<bool> {
   // Skip .git directory
   if path.components().any(|comp| comp.as_os_str() == ".git") {
     return Ok(true);
@@ -147,18 +385,35 @@ fn is_excluded_file(entry: &DirEntry, threshold_bytes: u64, include_all: bool) -
     return Ok(true);
   }
 
-  if include_all {
+  // Exclude patterns always win over include patterns
+  if filters
+    .exclude_set
+    .is_some_and(|set| set.is_match(relative_path))
+  {
+    return Ok(true);
+  }
+
+  // If any include patterns are given, a file must match at least one
+  if !is_dir
+    && filters
+      .include_set
+      .is_some_and(|set| !set.is_match(relative_path))
+  {
+    return Ok(true);
+  }
+
+  if filters.include_all {
     return Ok(false);
   }
 
   // Check if file is a directory
-  if entry.file_type().is_dir() {
+  if is_dir {
     return Ok(false);
   }
 
   // Check file size
-  let metadata = entry.metadata()?;
-  if metadata.len() > threshold_bytes {
+  let metadata = fs::metadata(path)?;
+  if metadata.len() > filters.threshold_bytes {
     debug!(
       "Skipping large file: {} ({})",
       path.display(),
@@ -176,86 +431,216 @@ fn is_excluded_file(entry: &DirEntry, threshold_bytes: u64, include_all: bool) -
   Ok(false)
 }
 
-#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-fn process_repository(
-  repo_dir: &Path,
-  output_path: &Path,
-  threshold_mb: f32,
-  include_all: bool,
-) -> Result<()> {
-  let threshold_bytes = (threshold_mb * 1024.0 * 1024.0) as u64;
-  info!(
-    "Processing repository with threshold: {}",
-    format_size(threshold_bytes, BINARY)
-  );
+fn slugify_anchor(text: &str) -> String {
+  text
+    .chars()
+    .filter_map(|ch| {
+      if ch.is_alphanumeric() || ch == '_' || ch == '-' {
+        Some(ch.to_ascii_lowercase())
+      } else if ch.is_whitespace() {
+        Some('-')
+      } else {
+        None
+      }
+    })
+    .collect()
+}
 
-  // Create output file
-  let file = File::create(output_path)?;
-  let output_file = Arc::new(Mutex::new(BufWriter::new(file)));
+/// Reads the whole file once, returning its bytes alongside the longest run
+/// of backticks found. A single pass covers both the Markdown fence-escaping
+/// scan and the content that gets written out, instead of reading the file
+/// from disk twice.
+fn read_file_and_backtick_run(path: &Path) -> Result<(Vec<u8>, usize)> {
+  let file = File::open(path)?;
+  let mut reader = BufReader::new(file);
+  let mut content = Vec::new();
+  reader.read_to_end(&mut content)?;
+
+  let mut longest = 0usize;
+  let mut current = 0usize;
+  for &byte in &content {
+    if byte == b'`' {
+      current += 1;
+      longest = longest.max(current);
+    } else {
+      current = 0;
+    }
+  }
 
-  // Collect all valid files first
-  let mut valid_entries = vec![];
-  for entry in WalkDir::new(repo_dir).into_iter().filter_map(Result::ok) {
-    if let Ok(false) = is_excluded_file(&entry, threshold_bytes, include_all) {
-      if entry.path().is_file() {
-        valid_entries.push(entry);
-      }
+  Ok((content, longest))
+}
+
+/// Fence one character longer than the longest backtick run in the file (and
+/// never shorter than three backticks), so it's never closed prematurely by
+/// the file's own content.
+fn fence_for_backtick_run(longest_run: usize) -> String {
+  "`".repeat((longest_run + 1).max(3))
+}
+
+fn stream_file_contents(path: &Path, writer: &mut impl Write) -> Result<()> {
+  let file = File::open(path)?;
+  let mut reader = BufReader::new(file);
+  let mut buffer = [0; 8192];
+
+  loop {
+    let bytes_read = reader.read(&mut buffer)?;
+    if bytes_read == 0 {
+      break; // End of file
     }
+
+    // Write the chunk directly to the output file
+    writer.write_all(&buffer[..bytes_read])?;
   }
 
-  info!("Found {} valid files to process", valid_entries.len());
+  Ok(())
+}
 
-  // Process files in parallel
-  valid_entries.par_iter().for_each(|entry| {
-    if let Err(err) = (|| -> Result<()> {
-      let path = entry.path();
-      let relative_path = path.strip_prefix(repo_dir)?;
+fn write_file_entry(
+  output: &mut impl Write,
+  base_dir: &Path,
+  path: &Path,
+  format: OutputFormat,
+) -> Result<()> {
+  let relative_path = path.strip_prefix(base_dir)?;
+
+  match format {
+    OutputFormat::Text => {
       let metadata = fs::metadata(path)?;
       let file_size = format_size(metadata.len(), BINARY);
-
-      // Get lock on output file only when writing
-      let mut output_guard = output_file.lock().unwrap();
       writeln!(
-        output_guard,
+        output,
         "================================================================================"
       )?;
-      writeln!(output_guard, "File: {}", relative_path.display())?;
-      writeln!(output_guard, "Size: {file_size}")?;
+      writeln!(output, "File: {}", relative_path.display())?;
+      writeln!(output, "Size: {file_size}")?;
       writeln!(
-        output_guard,
+        output,
         "================================================================================"
       )?;
+      stream_file_contents(path, output)?;
+      writeln!(output)?;
+      writeln!(output)?;
+    }
+    OutputFormat::Md => {
+      let (content, longest_run) = read_file_and_backtick_run(path)?;
+      let fence = fence_for_backtick_run(longest_run);
+      let lang = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+      writeln!(output, "### {}", relative_path.display())?;
+      writeln!(output)?;
+      writeln!(output, "{fence}{lang}")?;
+      output.write_all(&content)?;
+      writeln!(output, "{fence}")?;
+      writeln!(output)?;
+    }
+  }
 
-      // Stream the file content directly
-      {
-        let file = File::open(path)?;
-        let mut reader = BufReader::new(file);
-        let mut buffer = [0; 8192];
-
-        loop {
-          let bytes_read = reader.read(&mut buffer)?;
-          if bytes_read == 0 {
-            break; // End of file
-          }
-
-          // Write the chunk directly to the output file
-          output_guard.write_all(&buffer[..bytes_read])?;
+  Ok(())
+}
+
+fn collect_valid_entries(repo_dir: &Path, filters: &FilterConfig) -> Vec<PathBuf> {
+  let mut valid_entries = vec![];
+  if filters.no_ignore {
+    for entry in WalkDir::new(repo_dir).into_iter().filter_map(Result::ok) {
+      let path = entry.path();
+      let relative_path = path.strip_prefix(repo_dir).unwrap_or(path);
+      let is_dir = entry.file_type().is_dir();
+      if let Ok(false) = is_excluded_file(path, relative_path, is_dir, filters) {
+        if path.is_file() {
+          valid_entries.push(path.to_path_buf());
         }
       }
+    }
+  } else {
+    for entry in WalkBuilder::new(repo_dir)
+      .hidden(false)
+      .build()
+      .filter_map(Result::ok)
+    {
+      let path = entry.path();
+      let relative_path = path.strip_prefix(repo_dir).unwrap_or(path);
+      let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+      if let Ok(false) = is_excluded_file(path, relative_path, is_dir, filters) {
+        if path.is_file() {
+          valid_entries.push(path.to_path_buf());
+        }
+      }
+    }
+  }
+
+  valid_entries
+}
+
+fn process_repository(
+  repo_dir: &Path,
+  output_path: &Path,
+  filters: &FilterConfig,
+  format: OutputFormat,
+) -> Result<()> {
+  info!(
+    "Processing repository with threshold: {}",
+    format_size(filters.threshold_bytes, BINARY)
+  );
+
+  // Create output file
+  let file = File::create(output_path)?;
+  let output_file = Arc::new(Mutex::new(BufWriter::new(file)));
 
-      // Add a newline after the file content
+  // Collect all valid files first, sorted so the Table of Contents (Markdown
+  // format) and the file bodies below always agree on an order.
+  let mut valid_entries = collect_valid_entries(repo_dir, filters);
+  valid_entries.sort();
+
+  info!("Found {} valid files to process", valid_entries.len());
+
+  if format == OutputFormat::Md {
+    {
+      let mut output_guard = output_file.lock().unwrap();
+      writeln!(output_guard, "# Table of Contents")?;
       writeln!(output_guard)?;
+      for path in &valid_entries {
+        let relative_path = path.strip_prefix(repo_dir)?;
+        let relative_path_str = relative_path.display().to_string();
+        writeln!(
+          output_guard,
+          "- [{relative_path_str}](#{})",
+          slugify_anchor(&relative_path_str)
+        )?;
+      }
       writeln!(output_guard)?;
-
-      Ok(())
-    })() {
-      warn!("Error processing {}: {}", entry.path().display(), err);
     }
-  });
 
-  // Make sure to flush the buffer before finishing
-  let mut output_guard = output_file.lock().unwrap();
-  output_guard.flush()?;
+    // The Table of Contents needs the bodies in the same order, so render
+    // each file into its own buffer in parallel (rayon's map/collect
+    // preserves input order regardless of completion order) and write those
+    // buffers out sequentially. Only Md pays the full-repo memory cost.
+    let rendered: Vec<(&PathBuf, Result<Vec<u8>>)> = valid_entries
+      .par_iter()
+      .map(|path| {
+        let mut buffer = Vec::new();
+        let result = write_file_entry(&mut buffer, repo_dir, path, format).map(|()| buffer);
+        (path, result)
+      })
+      .collect();
+
+    let mut output_guard = output_file.lock().unwrap();
+    for (path, result) in rendered {
+      match result {
+        Ok(buffer) => output_guard.write_all(&buffer)?,
+        Err(err) => warn!("Error processing {}: {}", path.display(), err),
+      }
+    }
+    output_guard.flush()?;
+  } else {
+    // Text output never needed ordering guarantees, so stream each file
+    // straight into the locked writer instead of buffering the whole repo.
+    valid_entries.par_iter().for_each(|path| {
+      let mut output_guard = output_file.lock().unwrap();
+      if let Err(err) = write_file_entry(&mut *output_guard, repo_dir, path, format) {
+        warn!("Error processing {}: {}", path.display(), err);
+      }
+    });
+    output_file.lock().unwrap().flush()?;
+  }
 
   info!(
     "Repository converted and saved to: {}",
@@ -297,22 +682,125 @@ fn main() -> Result<()> {
   // Clone repository
   clone_repository(&repo_url, temp_dir.path())?;
 
+  // Parse after cloning so a repo-committed .gitsnap.toml/.gitsnap.yaml is picked up
+  let config = load_config(temp_dir.path())?;
+
+  let threshold = args.threshold.map_or_else(
+    || {
+      config.threshold.map_or_else(
+        || {
+          debug!("threshold: {DEFAULT_THRESHOLD} (default)");
+          DEFAULT_THRESHOLD
+        },
+        |value| {
+          debug!("threshold: {value} (from config file)");
+          value
+        },
+      )
+    },
+    |value| {
+      debug!("threshold: {value} (from CLI flag)");
+      value
+    },
+  );
+
+  let include_all = resolve_bool_flag("include_all", args.include_all, config.include_all);
+  let no_ignore = resolve_bool_flag("no_ignore", args.no_ignore, config.no_ignore);
+
+  let format = args.format.map_or_else(
+    || {
+      config.format.map_or_else(
+        || {
+          debug!("format: text (default)");
+          OutputFormat::Text
+        },
+        |value| {
+          debug!("format: {value:?} (from config file)");
+          value
+        },
+      )
+    },
+    |value| {
+      debug!("format: {value:?} (from CLI flag)");
+      value
+    },
+  );
+
+  let include_wiki = resolve_bool_flag("include_wiki", args.include_wiki, config.include_wiki);
+
+  let include_patterns = if !args.include.is_empty() {
+    debug!("include: {:?} (from CLI flag)", args.include);
+    args.include.clone()
+  } else if let Some(patterns) = &config.include {
+    debug!("include: {patterns:?} (from config file)");
+    patterns.clone()
+  } else {
+    Vec::new()
+  };
+
+  let exclude_patterns = if !args.exclude.is_empty() {
+    debug!("exclude: {:?} (from CLI flag)", args.exclude);
+    args.exclude.clone()
+  } else if let Some(patterns) = &config.exclude {
+    debug!("exclude: {patterns:?} (from config file)");
+    patterns.clone()
+  } else {
+    Vec::new()
+  };
+
   // Determine output file path
   let repo_name = extract_repo_name(&repo_url)?;
-  let output_path = match &args.output {
-    Some(path) => PathBuf::from(path),
-    None => PathBuf::from(format!("{repo_name}.txt")),
+  let output_path = match args.output.as_ref().or(config.output.as_ref()) {
+    Some(path) => {
+      debug!(
+        "output: {path} (from {})",
+        if args.output.is_some() { "CLI flag" } else { "config file" }
+      );
+      PathBuf::from(path)
+    }
+    None => {
+      debug!("output: {repo_name}.txt (default)");
+      PathBuf::from(format!("{repo_name}.txt"))
+    }
   };
 
   info!("Output will be saved to: {}", output_path.display());
 
+  #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  let threshold_bytes = (threshold * 1024.0 * 1024.0) as u64;
+  let include_set = if include_patterns.is_empty() {
+    None
+  } else {
+    Some(build_glob_set(&include_patterns)?)
+  };
+  let exclude_set = if exclude_patterns.is_empty() {
+    None
+  } else {
+    Some(build_glob_set(&exclude_patterns)?)
+  };
+
+  let filters = FilterConfig {
+    threshold_bytes,
+    include_all,
+    no_ignore,
+    include_set: include_set.as_ref(),
+    exclude_set: exclude_set.as_ref(),
+  };
+
   // Process repository and generate output file
-  process_repository(
-    temp_dir.path(),
-    &output_path,
-    args.threshold,
-    args.include_all,
-  )?;
+  process_repository(temp_dir.path(), &output_path, &filters, format)?;
+
+  if include_wiki {
+    let wiki_temp_dir = tempdir()?;
+    match clone_wiki_repository(&repo_url, wiki_temp_dir.path()) {
+      Ok(()) => match append_wiki_section(wiki_temp_dir.path(), &output_path, format, &filters) {
+        Ok(0) => warn!("Wiki repository has no Markdown pages, skipping wiki section"),
+        Ok(page_count) => info!("Appended {page_count} wiki page(s) to the snapshot"),
+        Err(err) => warn!("Failed to append wiki section: {err}"),
+      },
+      Err(err) => warn!("Could not include wiki (it may not exist): {err}"),
+    }
+  }
 
   info!(
     "Done! Repository contents saved to: {}",
@@ -320,3 +808,123 @@ fn main() -> Result<()> {
   );
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn slugify_anchor_lowercases_and_replaces_whitespace() {
+    assert_eq!(slugify_anchor("Hello World!"), "hello-world");
+    assert_eq!(slugify_anchor("src/main.rs"), "srcmainrs");
+    assert_eq!(slugify_anchor("a_b-C"), "a_b-c");
+  }
+
+  #[test]
+  fn fence_for_backtick_run_is_always_longer_than_the_content() {
+    assert_eq!(fence_for_backtick_run(0), "```");
+    assert_eq!(fence_for_backtick_run(2), "```");
+    assert_eq!(fence_for_backtick_run(3), "````");
+    assert_eq!(fence_for_backtick_run(5), "``````");
+  }
+
+  #[test]
+  fn read_file_and_backtick_run_finds_the_longest_run() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("snippet.md");
+    fs::write(&path, "plain\n```inner```\nmore ```` text").unwrap();
+
+    let (content, longest_run) = read_file_and_backtick_run(&path).unwrap();
+    assert_eq!(content, fs::read(&path).unwrap());
+    assert_eq!(longest_run, 4);
+  }
+
+  #[test]
+  fn transfer_percent_matches_ratio_and_avoids_division_by_zero() {
+    assert_eq!(transfer_percent(0, 0), 0);
+    assert_eq!(transfer_percent(50, 200), 25);
+    assert_eq!(transfer_percent(10, 10), 100);
+  }
+
+  #[test]
+  fn resolve_bool_flag_prefers_cli_then_config_then_default() {
+    assert!(resolve_bool_flag("x", true, Some(false)));
+    assert!(resolve_bool_flag("x", false, Some(true)));
+    assert!(!resolve_bool_flag("x", false, Some(false)));
+    assert!(!resolve_bool_flag("x", false, None));
+  }
+
+  fn no_filters() -> FilterConfig<'static> {
+    FilterConfig {
+      threshold_bytes: 1024,
+      include_all: false,
+      no_ignore: false,
+      include_set: None,
+      exclude_set: None,
+    }
+  }
+
+  #[test]
+  fn is_excluded_file_skips_git_and_node_modules_dirs() {
+    let filters = no_filters();
+    assert!(is_excluded_file(
+      Path::new("repo/.git/HEAD"),
+      Path::new(".git/HEAD"),
+      false,
+      &filters
+    )
+    .unwrap());
+    assert!(is_excluded_file(
+      Path::new("repo/node_modules/pkg/index.js"),
+      Path::new("node_modules/pkg/index.js"),
+      false,
+      &filters
+    )
+    .unwrap());
+  }
+
+  #[test]
+  fn is_excluded_file_applies_threshold() {
+    let dir = tempdir().unwrap();
+    let small = dir.path().join("small.txt");
+    let large = dir.path().join("large.txt");
+    fs::write(&small, "ok").unwrap();
+    fs::write(&large, "x".repeat(2048)).unwrap();
+
+    let filters = FilterConfig {
+      threshold_bytes: 1024,
+      ..no_filters()
+    };
+
+    assert!(!is_excluded_file(&small, Path::new("small.txt"), false, &filters).unwrap());
+    assert!(is_excluded_file(&large, Path::new("large.txt"), false, &filters).unwrap());
+  }
+
+  #[test]
+  fn is_excluded_file_include_must_match_and_exclude_always_wins() {
+    let dir = tempdir().unwrap();
+    let src_file = dir.path().join("main.rs");
+    let doc_file = dir.path().join("README.md");
+    fs::write(&src_file, "fn main() {}").unwrap();
+    fs::write(&doc_file, "docs").unwrap();
+
+    let include_set = build_glob_set(&["*.rs".to_string()]).unwrap();
+    let exclude_set = build_glob_set(&["*.rs".to_string()]).unwrap();
+
+    let include_only = FilterConfig {
+      include_set: Some(&include_set),
+      ..no_filters()
+    };
+    assert!(!is_excluded_file(&src_file, Path::new("main.rs"), false, &include_only).unwrap());
+    assert!(is_excluded_file(&doc_file, Path::new("README.md"), false, &include_only).unwrap());
+
+    let include_and_exclude = FilterConfig {
+      include_set: Some(&include_set),
+      exclude_set: Some(&exclude_set),
+      ..no_filters()
+    };
+    assert!(
+      is_excluded_file(&src_file, Path::new("main.rs"), false, &include_and_exclude).unwrap()
+    );
+  }
+}